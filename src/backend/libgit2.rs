@@ -0,0 +1,295 @@
+//! The default `RepoBackend`, backed by libgit2 (via the `git2` crate).
+//!
+//! As an internal optimization, status summaries can instead be computed
+//! by shelling out to the `git` binary (see `StatusBackend`), since it can
+//! leverage git's own on-disk status cache on very large repos.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use git2;
+
+use backend::RepoBackend;
+use repo::StatusSummary;
+
+/// Which implementation computes a repo's status.
+///
+/// `LibGit2` is the default, since it needs no external process. `Git`
+/// shells out to the `git` binary, which can be markedly faster on very
+/// large repos because it can use git's on-disk status cache.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StatusBackend {
+    LibGit2,
+    Git,
+}
+
+pub struct LibGit2Backend {
+    path: PathBuf,
+}
+
+/// Returns `true` for the strings git's config parser treats as boolean
+/// `false` (`false`, `no`, `off`, `0`, or empty), case-insensitively.
+fn is_falsy_git_bool(value: &str) -> bool {
+    value.is_empty()
+        || ["false", "no", "off", "0"]
+            .iter()
+            .any(|falsy| value.eq_ignore_ascii_case(falsy))
+}
+
+impl LibGit2Backend {
+    /// Returns the `git2::Repository` equivalent of this repo.
+    fn as_git2_repo(&self) -> git2::Repository {
+        git2::Repository::open(&self.path).ok().expect(
+            "Could not open {} as a git repo. Perhaps you should run \
+             `git global scan` again.",
+        )
+    }
+
+    /// Returns the configured `StatusBackend`, per `global.statusBackend`
+    /// in gitconfig (`git`/`libgit2`), falling back to `LibGit2` if unset
+    /// or if the `git` executable isn't on `PATH`.
+    fn status_backend(&self) -> StatusBackend {
+        let wants_git = git2::Config::open_default()
+            .ok()
+            .and_then(|cfg| cfg.get_string("global.statusbackend").ok())
+            .map(|backend| backend.eq_ignore_ascii_case("git"))
+            .unwrap_or(false);
+        if wants_git && Command::new("git").arg("--version").output().is_ok() {
+            StatusBackend::Git
+        } else {
+            StatusBackend::LibGit2
+        }
+    }
+
+    /// Returns `true` if the repo's `core.fsmonitor` (or the legacy
+    /// `core.fsMonitor` spelling) is configured truthy, meaning a status
+    /// query could spawn an external fsmonitor process as a side effect.
+    ///
+    /// `core.fsmonitor` is most commonly set to a hook-program path (e.g.
+    /// `core.fsmonitor = .git/hooks/fsmonitor`), not a boolean, so a
+    /// plain `get_bool` misses exactly the configs that spawn a process.
+    /// Read it as a string and treat anything but an explicit falsy git
+    /// boolean (`false`/`no`/`off`/`0`/empty) as truthy.
+    fn fsmonitor_configured(&self, git2_repo: &git2::Repository) -> bool {
+        let config = match git2_repo.config() {
+            Ok(config) => config,
+            Err(_) => return false,
+        };
+        match config.get_string("core.fsmonitor") {
+            Ok(value) => !is_falsy_git_bool(&value),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns `true` if the user has opted out of our default read-only
+    /// behavior and wants `core.fsmonitor` honored during scans.
+    fn honors_fsmonitor(&self) -> bool {
+        git2::Config::open_default()
+            .ok()
+            .and_then(|cfg| cfg.get_bool("global.honorfsmonitor").ok())
+            .unwrap_or(false)
+    }
+
+    /// Builds the `git status --porcelain=v2 --branch` command used by
+    /// the `Git` backend, with fsmonitor and hook execution disabled so a
+    /// read-only scan can't trigger user-configured external commands.
+    fn git_status_command(&self) -> Command {
+        let mut command = Command::new("git");
+        command
+            .arg("-c")
+            .arg("core.fsmonitor=false")
+            .arg("-c")
+            .arg("core.hooksPath=/dev/null")
+            .arg("status")
+            .arg("--porcelain=v2")
+            .arg("--branch")
+            .current_dir(&self.path);
+        command
+    }
+
+    fn stash_list_via_libgit2(&self) -> Vec<String> {
+        let mut stash = vec![];
+        self.as_git2_repo()
+            .stash_foreach(|index, name, _oid| {
+                stash.push(format!("stash@{{{}}}: {}", index, name));
+                true
+            })
+            .unwrap();
+        stash
+    }
+
+    fn num_commits_ahead_behind_via_libgit2(&self) -> Option<(usize, usize)> {
+        let git2_repo = self.as_git2_repo();
+        let head = git2_repo.head().ok()?;
+        if !head.is_branch() {
+            return None;
+        }
+        let local_oid = head.target()?;
+        let local_branch = git2::Branch::wrap(head);
+        let upstream = local_branch.upstream().ok()?;
+        let upstream_oid = upstream.get().target()?;
+        git2_repo
+            .graph_ahead_behind(local_oid, upstream_oid)
+            .ok()
+    }
+
+    /// Parses the `# branch.ab +N -M` header out of `git status
+    /// --porcelain=v2 --branch`. Returns `None` on any failure, or if the
+    /// branch has no upstream (no `branch.ab` header is emitted).
+    fn num_commits_ahead_behind_via_git(&self) -> Option<(usize, usize)> {
+        let output = self.git_status_command().output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        for line in stdout.lines() {
+            if line.starts_with("# branch.ab ") {
+                let rest = &line["# branch.ab ".len()..];
+                let mut ahead = 0;
+                let mut behind = 0;
+                for token in rest.split_whitespace() {
+                    if token.starts_with('+') {
+                        ahead = token[1..].parse().ok()?;
+                    } else if token.starts_with('-') {
+                        behind = token[1..].parse().ok()?;
+                    }
+                }
+                return Some((ahead, behind));
+            }
+        }
+        None
+    }
+
+    fn status_summary_via_libgit2(&self) -> StatusSummary {
+        let git2_repo = self.as_git2_repo();
+        let mut status_opts = git2::StatusOptions::new();
+        status_opts
+            .show(git2::StatusShow::IndexAndWorkdir)
+            .include_untracked(true)
+            .include_ignored(false);
+        let statuses = git2_repo
+            .statuses(Some(&mut status_opts))
+            .expect("Could not get statuses.");
+
+        let mut summary = StatusSummary::default();
+        for entry in statuses.iter() {
+            let status = entry.status();
+            if status.is_conflicted() {
+                summary.conflicted += 1;
+                continue;
+            }
+            if status.is_index_new()
+                || status.is_index_modified()
+                || status.is_index_deleted()
+                || status.is_index_renamed()
+                || status.is_index_typechange()
+            {
+                summary.staged += 1;
+            }
+            if status.is_wt_new() {
+                summary.untracked += 1;
+            }
+            if status.is_wt_modified() {
+                summary.modified += 1;
+            }
+            if status.is_wt_deleted() {
+                summary.deleted += 1;
+            }
+            if status.is_wt_renamed() {
+                summary.renamed += 1;
+            }
+            if status.is_wt_typechange() {
+                summary.typechanged += 1;
+            }
+        }
+        summary.stashed = self.stash_list_via_libgit2().len();
+        summary
+    }
+
+    /// Computes the `StatusSummary` by shelling out to `git status
+    /// --porcelain=v2 --branch`. Returns `None` on any failure so callers
+    /// can fall back to the libgit2 path.
+    fn status_summary_via_git(&self) -> Option<StatusSummary> {
+        let output = self.git_status_command().output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        let mut summary = StatusSummary::default();
+        for line in stdout.lines() {
+            let mut fields = line.split_whitespace();
+            match fields.next() {
+                Some("1") | Some("2") => {
+                    let xy = fields.next().unwrap_or("..");
+                    let mut chars = xy.chars();
+                    let x = chars.next().unwrap_or('.');
+                    let y = chars.next().unwrap_or('.');
+                    if x != '.' {
+                        summary.staged += 1;
+                    }
+                    match y {
+                        'M' => summary.modified += 1,
+                        'D' => summary.deleted += 1,
+                        'R' => summary.renamed += 1,
+                        'T' => summary.typechanged += 1,
+                        _ => {}
+                    }
+                }
+                Some("u") => summary.conflicted += 1,
+                Some("?") => summary.untracked += 1,
+                _ => {}
+            }
+        }
+        summary.stashed = self.stash_list_via_libgit2().len();
+        Some(summary)
+    }
+}
+
+impl RepoBackend for LibGit2Backend {
+    fn open(path: &Path) -> LibGit2Backend {
+        LibGit2Backend {
+            path: path.to_path_buf(),
+        }
+    }
+
+    fn head_commit_time(&self) -> Option<i64> {
+        let git2_repo = self.as_git2_repo();
+        let head = git2_repo.head().ok()?;
+        let oid = head.target()?;
+        let commit = git2_repo.find_commit(oid).ok()?;
+        Some(commit.time().seconds())
+    }
+
+    fn status_summary(&self) -> StatusSummary {
+        // A configured `core.fsmonitor` can spawn an external process as
+        // a side effect of libgit2's status walk. We have no way to
+        // neutralize that without writing to the repo's on-disk config
+        // (which we won't do), so unless `global.honorFsmonitor` is set,
+        // route around it by preferring the `git` shell-out, which
+        // disables it per invocation via `-c`. The libgit2 path is used
+        // when the user opts in, or when the shell-out fails (e.g. `git`
+        // is missing).
+        let git2_repo = self.as_git2_repo();
+        if self.status_backend() == StatusBackend::Git
+            || (self.fsmonitor_configured(&git2_repo) && !self.honors_fsmonitor())
+        {
+            if let Some(summary) = self.status_summary_via_git() {
+                return summary;
+            }
+        }
+        self.status_summary_via_libgit2()
+    }
+
+    fn stash_list(&self) -> Vec<String> {
+        self.stash_list_via_libgit2()
+    }
+
+    fn num_commits_ahead_behind(&self) -> Option<(usize, usize)> {
+        match self.status_backend() {
+            StatusBackend::Git => self
+                .num_commits_ahead_behind_via_git()
+                .unwrap_or_else(|| self.num_commits_ahead_behind_via_libgit2()),
+            StatusBackend::LibGit2 => self.num_commits_ahead_behind_via_libgit2(),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! VCS backend abstraction for the read-only operations git-global needs.
+//!
+//! `Repo` delegates its aggregate queries (status summary, stash list,
+//! ahead/behind, last-commit time) to a `RepoBackend` rather than calling
+//! libgit2 directly, so an alternative implementation -- e.g. a pure-Rust
+//! `gitoxide` backend, avoiding the C toolchain dependency -- can be added
+//! behind a Cargo feature without touching `Repo`'s callers.
+
+use std::path::Path;
+
+use repo::StatusSummary;
+
+pub mod libgit2;
+
+#[cfg(feature = "gitoxide")]
+pub mod gitoxide;
+
+#[cfg(not(feature = "gitoxide"))]
+pub use self::libgit2::LibGit2Backend as ActiveBackend;
+#[cfg(feature = "gitoxide")]
+pub use self::gitoxide::GitoxideBackend as ActiveBackend;
+
+/// Read-only VCS operations that back `Repo`'s reporting.
+pub trait RepoBackend {
+    /// Opens the repository at `path`.
+    fn open(path: &Path) -> Self
+    where
+        Self: Sized;
+
+    /// Returns the commit time of `HEAD`, in seconds since the epoch, or
+    /// `None` if `HEAD` is unborn or unresolvable.
+    fn head_commit_time(&self) -> Option<i64>;
+
+    /// Returns a `StatusSummary` of the index, working tree, and stash.
+    fn status_summary(&self) -> StatusSummary;
+
+    /// Returns the list of stash entries, most recent first.
+    fn stash_list(&self) -> Vec<String>;
+
+    /// Returns `(ahead, behind)` commit counts for `HEAD` against its
+    /// upstream, or `None` on detached HEAD or no configured upstream.
+    fn num_commits_ahead_behind(&self) -> Option<(usize, usize)>;
+}
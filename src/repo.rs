@@ -6,6 +6,8 @@ use std::path::PathBuf;
 
 use git2;
 
+use backend::{ActiveBackend, RepoBackend};
+
 /// A git repository, represented by the full path to its base directory.
 #[derive(Clone, Eq, Hash, PartialEq)]
 pub struct Repo {
@@ -27,28 +29,80 @@ impl Repo {
         )
     }
 
+    /// Returns the `RepoBackend` for this repo, i.e. `ActiveBackend`, which
+    /// is chosen at compile time. libgit2 is the only implementation in
+    /// the tree; no `gitoxide` backend module is present.
+    fn backend(&self) -> ActiveBackend {
+        ActiveBackend::open(&self.path)
+    }
+
     /// Returns the full path to the repo as a `String`.
     pub fn path(&self) -> String {
         self.path.to_str().unwrap().to_string()
     }
 
+    /// Returns the name of the current branch, or the short OID of `HEAD`
+    /// when it is detached.
+    pub fn current_branch(&self) -> Option<String> {
+        let git2_repo = self.as_git2_repo();
+        let head = git2_repo.head().ok()?;
+        if head.is_branch() {
+            head.shorthand().map(|name| name.to_string())
+        } else {
+            head.target().map(|oid| {
+                let sha = oid.to_string();
+                sha[..7.min(sha.len())].to_string()
+            })
+        }
+    }
+
+    /// Returns the name and URL of this repo's default remote: the
+    /// current branch's configured remote, or `origin` if the branch
+    /// doesn't track one.
+    pub fn default_remote(&self) -> Option<(String, String)> {
+        let git2_repo = self.as_git2_repo();
+        let remote_name = git2_repo
+            .head()
+            .ok()
+            .filter(|head| head.is_branch())
+            .and_then(|head| head.shorthand().map(|name| name.to_string()))
+            .and_then(|branch_name| {
+                git2_repo
+                    .branch_upstream_remote(&format!("refs/heads/{}", branch_name))
+                    .ok()
+                    .and_then(|buf| buf.as_str().map(|name| name.to_string()))
+            })
+            .unwrap_or_else(|| "origin".to_string());
+        let remote = git2_repo.find_remote(&remote_name).ok()?;
+        let url = remote.url()?.to_string();
+        Some((remote_name, url))
+    }
+
+    /// Returns the number of commits the current branch is ahead of and
+    /// behind its upstream, as `(ahead, behind)`.
+    ///
+    /// Returns `None` if `HEAD` is detached or has no configured upstream.
+    pub fn num_commits_ahead_behind(&self) -> Option<(usize, usize)> {
+        self.backend().num_commits_ahead_behind()
+    }
+
     /// Returns the age of the last commit in hours.
     pub fn num_hours_since_last_commit(&self) -> i64 {
-        // dbg!(&self.path);
-        let git2_repo = self.as_git2_repo();
-        // dbg!(git2_repo.state());
-        if let Ok(head) = git2_repo.head() {
-            if let Some(oid) = head.target() {
-                if let Ok(commit) = git2_repo.find_commit(oid) {
-                    let commit_time = Utc.timestamp(commit.time().seconds(), 0);
-                    let age_h = Utc::now()
-                        .signed_duration_since(commit_time)
-                        .num_hours();
-                    return age_h;
-                }
+        match self.backend().head_commit_time() {
+            Some(seconds) => {
+                let commit_time = Utc.timestamp(seconds, 0);
+                Utc::now()
+                    .signed_duration_since(commit_time)
+                    .num_hours()
             }
+            None => i64::max_value(),
         }
-        i64::max_value()
+    }
+
+    /// Returns a `StatusSummary` of the repo's index, working tree, and
+    /// stash.
+    pub fn get_status_summary(&self) -> StatusSummary {
+        self.backend().status_summary()
     }
 
     pub fn get_status(&self) -> Vec<String> {
@@ -96,14 +150,67 @@ impl Repo {
 
     /// Returns the list of stash entries for the repo.
     pub fn get_stash_list(&self) -> Vec<String> {
-        let mut stash = vec![];
-        self.as_git2_repo()
-            .stash_foreach(|index, name, _oid| {
-                stash.push(format!("stash@{{{}}}: {}", index, name));
-                true
-            })
-            .unwrap();
-        stash
+        self.backend().stash_list()
+    }
+}
+
+/// Counted summary of a repo's index, working tree, and stash state.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StatusSummary {
+    pub staged: usize,
+    pub modified: usize,
+    pub deleted: usize,
+    pub renamed: usize,
+    pub typechanged: usize,
+    pub untracked: usize,
+    pub conflicted: usize,
+    pub stashed: usize,
+}
+
+/// A compact, starship-prompt-style rendering, e.g. `!3 +2 ?1 $1` for 3
+/// modified, 2 staged, 1 untracked, and 1 stashed entry. Zero counts are
+/// omitted.
+impl fmt::Display for StatusSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = vec![];
+        if self.conflicted > 0 {
+            parts.push(format!("={}", self.conflicted));
+        }
+        if self.modified > 0 {
+            parts.push(format!("!{}", self.modified));
+        }
+        if self.staged > 0 {
+            parts.push(format!("+{}", self.staged));
+        }
+        if self.deleted > 0 {
+            parts.push(format!("\u{2718}{}", self.deleted));
+        }
+        if self.renamed > 0 {
+            parts.push(format!("\u{00bb}{}", self.renamed));
+        }
+        if self.typechanged > 0 {
+            parts.push(format!("T{}", self.typechanged));
+        }
+        if self.untracked > 0 {
+            parts.push(format!("?{}", self.untracked));
+        }
+        if self.stashed > 0 {
+            parts.push(format!("${}", self.stashed));
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// Formats an `(ahead, behind)` tuple as a `⇡n`/`⇣n` tracking indicator,
+/// showing both when the branch has diverged from its upstream. Returns
+/// an empty string when there's nothing to report (no upstream, or even
+/// with it).
+pub fn format_tracking(ahead_behind: Option<(usize, usize)>) -> String {
+    match ahead_behind {
+        Some((0, 0)) | None => "".to_string(),
+        Some((ahead, 0)) => format!("\u{21e1}{}", ahead),
+        Some((0, behind)) => format!("\u{21e3}{}", behind),
+        Some((ahead, behind)) => format!("\u{21e1}{} \u{21e3}{}", ahead, behind),
     }
 }
 
@@ -0,0 +1,104 @@
+//! Aggregates per-repo data into a single report for the `info`, `list`,
+//! and `status` subcommands, in both text and JSON form.
+
+use rayon::prelude::*;
+
+use repo::{format_tracking, Repo, StatusSummary};
+
+/// One repo's worth of gathered data, ready to print.
+struct ReportRow {
+    path: String,
+    branch: Option<String>,
+    remote: Option<(String, String)>,
+    ahead_behind: Option<(usize, usize)>,
+    status: StatusSummary,
+    hours_since_last_commit: i64,
+}
+
+/// A report summarizing every repo git-global knows about.
+pub struct Report {
+    rows: Vec<ReportRow>,
+}
+
+impl Report {
+    /// Gathers a `ReportRow` for each repo across a bounded thread pool
+    /// (each `Repo` opens its own, independent backend), then sorts by
+    /// path so output stays stable across runs.
+    pub fn new(repos: &[Repo]) -> Report {
+        let mut rows: Vec<ReportRow> = repos
+            .par_iter()
+            .map(|repo| ReportRow {
+                path: repo.path(),
+                branch: repo.current_branch(),
+                remote: repo.default_remote(),
+                ahead_behind: repo.num_commits_ahead_behind(),
+                status: repo.get_status_summary(),
+                hours_since_last_commit: repo.num_hours_since_last_commit(),
+            })
+            .collect();
+        rows.sort_by(|a, b| a.path.cmp(&b.path));
+        Report { rows }
+    }
+
+    /// Prints a human-readable summary, one repo per line.
+    pub fn print(&self) {
+        for row in &self.rows {
+            let mut line = row.path.clone();
+            if let Some(ref branch) = row.branch {
+                line.push_str(&format!(" [{}]", branch));
+            }
+            if let Some((ref name, ref url)) = row.remote {
+                line.push_str(&format!(" -> {} ({})", name, url));
+            }
+            let tracking = format_tracking(row.ahead_behind);
+            if !tracking.is_empty() {
+                line.push_str(&format!(" {}", tracking));
+            }
+            let status = row.status.to_string();
+            if !status.is_empty() {
+                line.push_str(&format!(" {}", status));
+            }
+            println!("{}", line);
+        }
+    }
+
+    /// Prints the same data as a JSON object whose `repos` key holds one
+    /// entry per repo. Unlike the compact symbolic
+    /// text form, `status` here is the full numeric breakdown so
+    /// downstream tooling can consume structured counts directly rather
+    /// than parsing the symbolic rendering.
+    pub fn print_json(&self) {
+        let repos: Vec<_> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let (ahead, behind) = row.ahead_behind.unwrap_or((0, 0));
+                let (remote_name, remote_url) = row
+                    .remote
+                    .clone()
+                    .unwrap_or_else(|| ("".to_string(), "".to_string()));
+                object!{
+                    "path" => row.path.clone(),
+                    "branch" => row.branch.clone().unwrap_or_else(|| "".to_string()),
+                    "remote_name" => remote_name,
+                    "remote_url" => remote_url,
+                    "ahead" => ahead,
+                    "behind" => behind,
+                    "hours_since_last_commit" => row.hours_since_last_commit,
+                    "status" => object!{
+                        "staged" => row.status.staged,
+                        "modified" => row.status.modified,
+                        "deleted" => row.status.deleted,
+                        "renamed" => row.status.renamed,
+                        "typechanged" => row.status.typechanged,
+                        "untracked" => row.status.untracked,
+                        "conflicted" => row.status.conflicted,
+                        "stashed" => row.status.stashed
+                    }
+                }
+            })
+            .collect();
+        let json = object!{ "repos" => repos };
+        println!("{:#}", json);
+    }
+}
@@ -8,7 +8,8 @@ use cursive::views::{Dialog, LinearLayout, TextView};
 use cursive::Cursive;
 use cursive_table_view::{TableView, TableViewItem};
 use errors::Result;
-use repo::Repo;
+use rayon::prelude::*;
+use repo::{format_tracking, Repo};
 use report::Report;
 use std::cmp::Ordering;
 
@@ -35,6 +36,8 @@ enum BasicColumn {
     Path,
     State,
     LastCommit,
+    Tracking,
+    Branch,
 }
 
 impl BasicColumn {
@@ -43,6 +46,8 @@ impl BasicColumn {
             BasicColumn::Path => "Directory",
             BasicColumn::State => "State",
             BasicColumn::LastCommit => "Last commit",
+            BasicColumn::Tracking => "Tracking",
+            BasicColumn::Branch => "Branch",
         }
     }
 }
@@ -53,6 +58,8 @@ struct Row {
     name: String,
     state: String,
     last_commit: String,
+    tracking: String,
+    branch: String,
 }
 
 impl TableViewItem<BasicColumn> for Row {
@@ -61,6 +68,8 @@ impl TableViewItem<BasicColumn> for Row {
             BasicColumn::Path => self.name.to_string(),
             BasicColumn::State => self.state.to_string(),
             BasicColumn::LastCommit => self.last_commit.to_string(),
+            BasicColumn::Tracking => self.tracking.to_string(),
+            BasicColumn::Branch => self.branch.to_string(),
         }
     }
 
@@ -72,6 +81,8 @@ impl TableViewItem<BasicColumn> for Row {
             BasicColumn::Path => self.name.cmp(&other.name),
             BasicColumn::State => self.state.cmp(&other.state),
             BasicColumn::LastCommit => self.last_commit.cmp(&other.last_commit),
+            BasicColumn::Tracking => self.tracking.cmp(&other.tracking),
+            BasicColumn::Branch => self.branch.cmp(&other.branch),
         }
     }
 }
@@ -89,23 +100,32 @@ pub fn execute(mut config: Config) -> Result<Report> {
 
     // -- table
     let mut table = TableView::<Row, BasicColumn>::new()
-        .column(BasicColumn::Path, "Directory", |c| c.width_percent(60))
+        .column(BasicColumn::Path, "Directory", |c| c.width_percent(50))
         .column(BasicColumn::State, "State", |c| c.align(HAlign::Center))
         .column(BasicColumn::LastCommit, "Last Commit", |c| {
             c.ordering(Ordering::Greater)
                 .align(HAlign::Right)
                 .width_percent(20)
-        });
+        })
+        .column(BasicColumn::Tracking, "Tracking", |c| {
+            c.align(HAlign::Center)
+        })
+        .column(BasicColumn::Branch, "Branch", |c| c.width_percent(10));
 
-    let rows = repos
-        .iter()
+    let mut rows: Vec<Row> = repos
+        .par_iter()
         .map(|repo| Row {
             repo: repo.clone(),
             name: repo.path().to_string(),
-            state: ".".to_string(), // repo.get_short_status(),
+            state: repo.get_status_summary().to_string(),
             last_commit: format!("{}", repo.num_hours_since_last_commit()),
+            tracking: format_tracking(repo.num_commits_ahead_behind()),
+            branch: repo.current_branch().unwrap_or_else(|| "?".to_string()),
         })
         .collect();
+    // Each row is gathered independently on the pool, so sort by path
+    // afterwards to keep table output stable across runs.
+    rows.sort_by(|a, b| a.name.cmp(&b.name));
     table.set_items(rows);
 
     table.set_on_sort(